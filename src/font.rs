@@ -0,0 +1,50 @@
+//! ASCII-to-segment-bitmask lookup table used by `write_str`.
+//!
+//! Each mask's bits correspond directly to the segments accepted by the
+//! `DIGIT_n_CTL` commands: bit0=a, bit1=b, bit2=c, bit3=d, bit4=e, bit5=f,
+//! bit6=g. The decimal point is controlled separately, via
+//! `write_punctuation`.
+
+/// Look up the segment bitmask for a supported ASCII character.
+///
+/// Returns `None` if the character has no seven-segment representation.
+pub(crate) const fn segment_mask(c: char) -> Option<u8> {
+    Some(match c {
+        '0' => 0x3F,
+        '1' => 0x06,
+        '2' => 0x5B,
+        '3' => 0x4F,
+        '4' => 0x66,
+        '5' => 0x6D,
+        '6' => 0x7D,
+        '7' => 0x07,
+        '8' => 0x7F,
+        '9' => 0x6F,
+        'A' | 'a' => 0x77,
+        'B' | 'b' => 0x7C,
+        'C' | 'c' => 0x39,
+        'D' | 'd' => 0x5E,
+        'E' | 'e' => 0x79,
+        'F' | 'f' => 0x71,
+        'G' | 'g' => 0x3D,
+        'H' | 'h' => 0x76,
+        'I' | 'i' => 0x06,
+        'J' | 'j' => 0x1E,
+        'L' | 'l' => 0x38,
+        'N' | 'n' => 0x54,
+        'O' | 'o' => 0x3F,
+        'P' | 'p' => 0x73,
+        'Q' | 'q' => 0x67,
+        'R' | 'r' => 0x50,
+        'S' | 's' => 0x6D,
+        'T' | 't' => 0x78,
+        'U' | 'u' => 0x3E,
+        'Y' | 'y' => 0x6E,
+        'Z' | 'z' => 0x5B,
+        ' ' => 0x00,
+        '-' => 0x40,
+        '_' => 0x08,
+        '\'' => 0x20,
+        _ => return None,
+    })
+}
@@ -1,20 +1,23 @@
-use crate::{Error, SevenSegInterface};
+use crate::{Error, NoDelay, SevenSegInterface};
+use embedded_hal::blocking::delay::DelayUs;
+#[cfg(not(feature = "eh1"))]
 use embedded_hal::blocking::i2c::Write;
+#[cfg(feature = "eh1")]
+use eh1::i2c::I2c;
 
 #[non_exhaustive]
 pub enum I2cError<I2C> {
     I2c(I2C),
 }
 
-pub struct SevSegI2c<I2C> {
+pub struct SevSegI2c<I2C, D = NoDelay> {
     i2c: I2C,
     addr: u8,
+    delay: D,
+    delay_us: u8,
 }
 
-impl<I2C> SevSegI2c<I2C>
-where
-    I2C: Write,
-{
+impl<I2C> SevSegI2c<I2C, NoDelay> {
     /// Create a new SparkFun Serial Seven Segment display using an I2C
     /// port. The I2C port supports 100kHz and 400kHz modes.
     ///
@@ -24,9 +27,34 @@ where
         Self {
             i2c,
             addr: addr.unwrap_or(0x71),
+            delay: NoDelay,
+            delay_us: 15,
         }
     }
+}
 
+impl<I2C, D> SevSegI2c<I2C, D>
+where
+    D: DelayUs<u8>,
+{
+    /// Create a new SparkFun Serial Seven Segment display using an I2C
+    /// port, with a delay provider used to insert a guard delay (default
+    /// ~15us) between back-to-back commands. This avoids potential NACKs
+    /// on buses fast enough to outrun the display's command processing.
+    ///
+    /// If no address is supplied, the default 7-bit address of `0x71`
+    /// will be used.
+    pub fn new_with_delay(i2c: I2C, addr: Option<u8>, delay: D) -> Self {
+        Self {
+            i2c,
+            addr: addr.unwrap_or(0x71),
+            delay,
+            delay_us: 15,
+        }
+    }
+}
+
+impl<I2C, D> SevSegI2c<I2C, D> {
     /// Update the address of the display used by the library.
     ///
     /// This does NOT reconfigure the display to use this new address.
@@ -37,22 +65,66 @@ where
         self.addr = addr;
     }
 
+    /// Change the guard delay (in microseconds) inserted between
+    /// back-to-back commands. Defaults to 15us. Has no effect unless this
+    /// was constructed with `new_with_delay()`.
+    pub fn set_command_delay(&mut self, delay_us: u8) {
+        self.delay_us = delay_us;
+    }
+
     /// Release the components
     pub fn release(self) -> I2C {
         self.i2c
     }
 }
 
-impl<I2C> SevenSegInterface for SevSegI2c<I2C>
+#[cfg(not(feature = "eh1"))]
+impl<I2C, D> SevenSegInterface for SevSegI2c<I2C, D>
 where
     I2C: Write,
+    D: DelayUs<u8>,
 {
     type InterfaceError = I2cError<I2C::Error>;
 
     fn send(&mut self, data: &[u8]) -> Result<(), Error<Self::InterfaceError>> {
         self.i2c
-            .write(self.addr, &data)
+            .write(self.addr, data)
             .map_err(|e| Error::Interface(I2cError::I2c(e)))
             .map(drop)
     }
+
+    fn command_delay(&mut self) {
+        self.delay.delay_us(self.delay_us);
+    }
+
+    fn configure_i2c_address(&mut self, new_addr: u8) -> Result<(), Error<Self::InterfaceError>> {
+        self.send(&[crate::command::I2C_ADDR_CFG, new_addr])?;
+        self.addr = new_addr;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<I2C, D> SevenSegInterface for SevSegI2c<I2C, D>
+where
+    I2C: I2c,
+    D: DelayUs<u8>,
+{
+    type InterfaceError = I2cError<I2C::Error>;
+
+    fn send(&mut self, data: &[u8]) -> Result<(), Error<Self::InterfaceError>> {
+        self.i2c
+            .write(self.addr, data)
+            .map_err(|e| Error::Interface(I2cError::I2c(e)))
+    }
+
+    fn command_delay(&mut self) {
+        self.delay.delay_us(self.delay_us);
+    }
+
+    fn configure_i2c_address(&mut self, new_addr: u8) -> Result<(), Error<Self::InterfaceError>> {
+        self.send(&[crate::command::I2C_ADDR_CFG, new_addr])?;
+        self.addr = new_addr;
+        Ok(())
+    }
 }
@@ -1,15 +1,16 @@
 //! Driver for the [SparkFun Serial 7 Segment Display](https://github.com/sparkfun/Serial7SegmentDisplay/wiki/Serial-7-Segment-Display-Datasheet)
 //!
-//! This is compatible with `embedded-hal`.
+//! This is compatible with `embedded-hal` 0.2 by default. Enable the `eh1`
+//! feature to use the `embedded-hal` 1.0 traits instead (I2C and SPI only).
 //!
-//! Right now, only the SPI or I2C interfaces are supported. In the future,
-//! support will be added for UART interfaces
+//! SPI, I2C, and UART interfaces are all supported.
 
 #![no_std]
 
 use bitflags::bitflags;
 pub mod i2c;
 pub mod spi;
+pub mod uart;
 
 bitflags! {
     /// A bit packed structure representing days of the week
@@ -24,6 +25,34 @@ bitflags! {
     }
 }
 
+/// Whether unused leading digit positions are left as zeroes or blanked
+/// out, used by the `_filled` family of numeric display methods.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Fill {
+    Zero,
+    Space,
+}
+
+/// Baud rates accepted by the display's `BAUD_RATE_CFG` command.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[repr(u8)]
+pub enum BaudRate {
+    Baud2400 = 0,
+    Baud4800 = 1,
+    Baud9600 = 2,
+    Baud14400 = 3,
+    Baud19200 = 4,
+    Baud38400 = 5,
+    Baud57600 = 6,
+    Baud76800 = 7,
+    Baud115200 = 8,
+    Baud230400 = 9,
+    Baud460800 = 10,
+    Baud921600 = 11,
+}
+
+mod font;
+
 mod command {
     #![allow(dead_code)]
 
@@ -51,6 +80,25 @@ pub enum Error<I> {
     DigitOutOfRange,
 }
 
+/// The number of decimal digits needed to represent `n` (at least 1).
+fn digit_count(mut n: u16) -> usize {
+    let mut count = 1;
+    while n >= 10 {
+        n /= 10;
+        count += 1;
+    }
+    count
+}
+
+/// A no-op guard delay, used by backends that were not constructed with a
+/// delay provider via `new_with_delay()`.
+#[derive(Debug, Default)]
+pub struct NoDelay;
+
+impl embedded_hal::blocking::delay::DelayUs<u8> for NoDelay {
+    fn delay_us(&mut self, _us: u8) {}
+}
+
 pub trait SevenSegInterface {
     /// A single error type used by the interface
     type InterfaceError;
@@ -58,6 +106,11 @@ pub trait SevenSegInterface {
     /// Sending commands to the interface
     fn send(&mut self, data: &[u8]) -> Result<(), Error<Self::InterfaceError>>;
 
+    /// Insert the guard delay between back-to-back commands, if one has
+    /// been configured. Backends constructed with a delay provider
+    /// (see `new_with_delay()`) override this; by default it is a no-op.
+    fn command_delay(&mut self) {}
+
     /// Set the digit cursor to a particular location
     /// `col` may be 0..=3, from left to right.
     fn set_cursor(&mut self, col: u8) -> Result<(), Error<Self::InterfaceError>> {
@@ -117,6 +170,7 @@ pub trait SevenSegInterface {
             }
         }
 
+        self.command_delay();
         self.send(digits)
     }
 
@@ -129,10 +183,7 @@ pub trait SevenSegInterface {
         }
 
         self.set_cursor(0)?;
-
-        // TODO: We seem to need roughly 15uS between
-        // back-to-back commands. How should I handle this?
-        // Failure to do so can cause a potential NACK.
+        self.command_delay();
 
         let data: [u8; 4] = [
             (num / 1000) as u8,
@@ -143,4 +194,185 @@ pub trait SevenSegInterface {
 
         self.send(&data)
     }
+
+    /// Write up to four characters to the display, one per digit, by
+    /// directly controlling each digit's segments. Used to render
+    /// characters that have no hex digit encoding, such as letters, `-`,
+    /// and space.
+    ///
+    /// Returns `Error::DigitOutOfRange` if `chars` is longer than 4
+    /// characters, or contains a character with no seven-segment
+    /// representation.
+    fn write_segment_chars(&mut self, chars: &[char]) -> Result<(), Error<Self::InterfaceError>> {
+        const DIGIT_CTL: [u8; 4] = [
+            command::DIGIT_1_CTL,
+            command::DIGIT_2_CTL,
+            command::DIGIT_3_CTL,
+            command::DIGIT_4_CTL,
+        ];
+
+        if chars.len() > DIGIT_CTL.len() {
+            return Err(Error::DigitOutOfRange);
+        }
+
+        for (ctl, c) in DIGIT_CTL.iter().zip(chars.iter()) {
+            let mask = font::segment_mask(*c).ok_or(Error::DigitOutOfRange)?;
+            self.send(&[*ctl, mask])?;
+            self.command_delay();
+        }
+
+        Ok(())
+    }
+
+    /// Write arbitrary ASCII text (up to 4 characters) to the display.
+    /// This is not limited to the hex digits 0x0..=0xF like
+    /// `write_digits`: any character with a seven-segment representation
+    /// (0-9, many letters, space, `-`, `_`, ...) may be used.
+    ///
+    /// Returns `Error::DigitOutOfRange` if `text` is longer than 4
+    /// characters, or contains a character with no seven-segment
+    /// representation.
+    fn write_str(&mut self, text: &str) -> Result<(), Error<Self::InterfaceError>> {
+        let mut chars = [' '; 4];
+        let mut len = 0;
+
+        for c in text.chars() {
+            if len >= chars.len() {
+                return Err(Error::DigitOutOfRange);
+            }
+            chars[len] = c;
+            len += 1;
+        }
+
+        self.write_segment_chars(&chars[..len])
+    }
+
+    /// Write a signed number to the display, left-filled with zeroes.
+    /// Negative numbers render a leading minus sign in place of the
+    /// leftmost unused digit. After this function, the cursor position is
+    /// undefined.
+    ///
+    /// Returns `Error::DigitOutOfRange` if `num` (plus its sign, if
+    /// negative) would not fit in 4 digits.
+    fn set_inum(&mut self, num: i16) -> Result<(), Error<Self::InterfaceError>> {
+        self.set_inum_filled(num, Fill::Zero)
+    }
+
+    /// As [`SevenSegInterface::set_inum`], but unused leading digit
+    /// positions are controlled by `fill` instead of always being
+    /// zero-filled.
+    fn set_inum_filled(
+        &mut self,
+        num: i16,
+        fill: Fill,
+    ) -> Result<(), Error<Self::InterfaceError>> {
+        let negative = num < 0;
+        let mag = num.unsigned_abs();
+
+        if mag > 9999 {
+            return Err(Error::DigitOutOfRange);
+        }
+
+        let digit_len = digit_count(mag);
+        let max_digits = if negative { 3 } else { 4 };
+
+        if digit_len > max_digits {
+            return Err(Error::DigitOutOfRange);
+        }
+
+        let fill_char = match fill {
+            Fill::Zero => '0',
+            Fill::Space => ' ',
+        };
+
+        let mut chars = [fill_char; 4];
+
+        // The sign always goes in the leftmost column, never to the
+        // right of any padding, so it reads as e.g. `-005` rather than
+        // the garbled `00-5`.
+        if negative {
+            chars[0] = '-';
+        }
+
+        let mut divisor = 10u16.pow((digit_len - 1) as u32);
+        let mut rem = mag;
+        for slot in chars.iter_mut().skip(4 - digit_len) {
+            *slot = (b'0' + (rem / divisor) as u8) as char;
+            rem %= divisor;
+            divisor /= 10;
+        }
+
+        self.write_segment_chars(&chars)
+    }
+
+    /// Write a fixed-point value to the display, left-filled with
+    /// zeroes. `value` is the number scaled by `10^decimals` (e.g.
+    /// `value = 314, decimals = 2` renders `3.14`), and negative values
+    /// render a leading minus sign as in `set_inum`.
+    ///
+    /// Returns `Error::DigitOutOfRange` if `value` (plus its sign, if
+    /// negative) would not fit in 4 digits, or if `decimals` is greater
+    /// than 3.
+    fn set_fixed(&mut self, value: i32, decimals: u8) -> Result<(), Error<Self::InterfaceError>> {
+        self.set_fixed_filled(value, decimals, Fill::Zero)
+    }
+
+    /// As [`SevenSegInterface::set_fixed`], with an explicit `fill`
+    /// argument. Leading digit positions are always zero-filled.
+    ///
+    /// `fill` must be `Fill::Zero`: the decimal point is placed at a
+    /// fixed column counted from the rightmost digit, so blanking
+    /// leading digits would make the value ambiguous (`0.05` would be
+    /// indistinguishable from `0.5`). `Fill::Space` returns
+    /// `Error::DigitOutOfRange`.
+    fn set_fixed_filled(
+        &mut self,
+        value: i32,
+        decimals: u8,
+        fill: Fill,
+    ) -> Result<(), Error<Self::InterfaceError>> {
+        if fill == Fill::Space {
+            return Err(Error::DigitOutOfRange);
+        }
+
+        let dot = match decimals {
+            0 => PunctuationFlags::NONE,
+            1 => PunctuationFlags::DOT_BETWEEN_3_AND_4,
+            2 => PunctuationFlags::DOT_BETWEEN_2_AND_3,
+            3 => PunctuationFlags::DOT_BETWEEN_1_AND_2,
+            _ => return Err(Error::DigitOutOfRange),
+        };
+
+        let negative = value < 0;
+        let mag: u16 = value
+            .unsigned_abs()
+            .try_into()
+            .map_err(|_| Error::DigitOutOfRange)?;
+        let num = i16::try_from(mag).map_err(|_| Error::DigitOutOfRange)?;
+        let num = if negative { -num } else { num };
+
+        self.set_inum_filled(num, fill)?;
+        self.write_punctuation(dot)
+    }
+
+    /// Reconfigure the address the display listens at on its I2C bus.
+    /// This command is transmitted over whatever transport `send` uses,
+    /// but only affects how the display responds on its I2C pins.
+    /// Backends that track the address locally (see `i2c::SevSegI2c`)
+    /// update their own state to match, so subsequent calls keep
+    /// targeting the reconfigured device.
+    fn configure_i2c_address(&mut self, new_addr: u8) -> Result<(), Error<Self::InterfaceError>> {
+        self.send(&[command::I2C_ADDR_CFG, new_addr])
+    }
+
+    /// Change the baud rate used by the display's UART interface.
+    fn configure_baud_rate(&mut self, rate: BaudRate) -> Result<(), Error<Self::InterfaceError>> {
+        self.send(&[command::BAUD_RATE_CFG, rate as u8])
+    }
+
+    /// Restore the display to its factory default settings (I2C address,
+    /// baud rate, brightness, etc).
+    fn factory_reset(&mut self) -> Result<(), Error<Self::InterfaceError>> {
+        self.send(&[command::FACTORY_RESET])
+    }
 }
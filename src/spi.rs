@@ -1,26 +1,74 @@
-use crate::{Error, SevenSegInterface};
+use crate::{Error, NoDelay, SevenSegInterface};
+use embedded_hal::blocking::delay::DelayUs;
+
+#[cfg(not(feature = "eh1"))]
 use embedded_hal::{blocking::spi::Write, digital::v2::OutputPin};
+#[cfg(feature = "eh1")]
+use eh1::spi::SpiDevice;
 
+#[cfg(not(feature = "eh1"))]
 #[non_exhaustive]
 pub enum SpimError<SPIM, GPIO> {
     Spim(SPIM),
     Gpio(GPIO),
 }
 
-pub struct SevSegSpim<SPIM, CS> {
+#[cfg(feature = "eh1")]
+#[non_exhaustive]
+pub enum SpimError<SPIM> {
+    Spim(SPIM),
+}
+
+#[cfg(not(feature = "eh1"))]
+pub struct SevSegSpim<SPIM, CS, D = NoDelay> {
     spim: SPIM,
     csn: CS,
+    delay: D,
+    delay_us: u8,
 }
 
-impl<SPIM, CS> SevSegSpim<SPIM, CS>
-where
-    SPIM: Write<u8>,
-    CS: OutputPin,
-{
+#[cfg(not(feature = "eh1"))]
+impl<SPIM, CS> SevSegSpim<SPIM, CS, NoDelay> {
     /// Create a new SparkFun Serial Seven Segment display using a SPI (Master)
     /// port. The SPI port has a maximum frequency of 250kHz, and must be in Mode 0.
     pub fn new(spim: SPIM, csn: CS) -> Self {
-        Self { spim, csn }
+        Self {
+            spim,
+            csn,
+            delay: NoDelay,
+            delay_us: 15,
+        }
+    }
+}
+
+#[cfg(not(feature = "eh1"))]
+impl<SPIM, CS, D> SevSegSpim<SPIM, CS, D>
+where
+    D: DelayUs<u8>,
+{
+    /// Create a new SparkFun Serial Seven Segment display using a SPI
+    /// (Master) port, with a delay provider used to insert a guard delay
+    /// (default ~15us) between back-to-back commands. This avoids
+    /// potential NACKs on buses fast enough to outrun the display's
+    /// command processing. The SPI port has a maximum frequency of
+    /// 250kHz, and must be in Mode 0.
+    pub fn new_with_delay(spim: SPIM, csn: CS, delay: D) -> Self {
+        Self {
+            spim,
+            csn,
+            delay,
+            delay_us: 15,
+        }
+    }
+}
+
+#[cfg(not(feature = "eh1"))]
+impl<SPIM, CS, D> SevSegSpim<SPIM, CS, D> {
+    /// Change the guard delay (in microseconds) inserted between
+    /// back-to-back commands. Defaults to 15us. Has no effect unless this
+    /// was constructed with `new_with_delay()`.
+    pub fn set_command_delay(&mut self, delay_us: u8) {
+        self.delay_us = delay_us;
     }
 
     /// Release the components
@@ -29,10 +77,12 @@ where
     }
 }
 
-impl<SPIM, CS> SevenSegInterface for SevSegSpim<SPIM, CS>
+#[cfg(not(feature = "eh1"))]
+impl<SPIM, CS, D> SevenSegInterface for SevSegSpim<SPIM, CS, D>
 where
     SPIM: Write<u8>,
     CS: OutputPin,
+    D: DelayUs<u8>,
 {
     type InterfaceError = SpimError<SPIM::Error, CS::Error>;
 
@@ -43,7 +93,7 @@ where
 
         let ret = self
             .spim
-            .write(&data)
+            .write(data)
             .map_err(|e| Error::Interface(SpimError::Spim(e)))
             .map(drop);
 
@@ -53,4 +103,81 @@ where
 
         ret
     }
+
+    fn command_delay(&mut self) {
+        self.delay.delay_us(self.delay_us);
+    }
+}
+
+#[cfg(feature = "eh1")]
+pub struct SevSegSpim<SPIM, D = NoDelay> {
+    spim: SPIM,
+    delay: D,
+    delay_us: u8,
+}
+
+#[cfg(feature = "eh1")]
+impl<SPIM> SevSegSpim<SPIM, NoDelay> {
+    /// Create a new SparkFun Serial Seven Segment display using a SPI
+    /// device. Chip-select is managed by the `SpiDevice` bus abstraction,
+    /// so no separate CS pin is needed here. The SPI port has a maximum
+    /// frequency of 250kHz, and must be in Mode 0.
+    pub fn new(spim: SPIM) -> Self {
+        Self {
+            spim,
+            delay: NoDelay,
+            delay_us: 15,
+        }
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<SPIM, D> SevSegSpim<SPIM, D>
+where
+    D: DelayUs<u8>,
+{
+    /// Create a new SparkFun Serial Seven Segment display using a SPI
+    /// device, with a delay provider used to insert a guard delay
+    /// (default ~15us) between back-to-back commands.
+    pub fn new_with_delay(spim: SPIM, delay: D) -> Self {
+        Self {
+            spim,
+            delay,
+            delay_us: 15,
+        }
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<SPIM, D> SevSegSpim<SPIM, D> {
+    /// Change the guard delay (in microseconds) inserted between
+    /// back-to-back commands. Defaults to 15us. Has no effect unless this
+    /// was constructed with `new_with_delay()`.
+    pub fn set_command_delay(&mut self, delay_us: u8) {
+        self.delay_us = delay_us;
+    }
+
+    /// Release the components
+    pub fn release(self) -> SPIM {
+        self.spim
+    }
+}
+
+#[cfg(feature = "eh1")]
+impl<SPIM, D> SevenSegInterface for SevSegSpim<SPIM, D>
+where
+    SPIM: SpiDevice,
+    D: DelayUs<u8>,
+{
+    type InterfaceError = SpimError<SPIM::Error>;
+
+    fn send(&mut self, data: &[u8]) -> Result<(), Error<Self::InterfaceError>> {
+        self.spim
+            .write(data)
+            .map_err(|e| Error::Interface(SpimError::Spim(e)))
+    }
+
+    fn command_delay(&mut self) {
+        self.delay.delay_us(self.delay_us);
+    }
 }
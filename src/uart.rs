@@ -0,0 +1,78 @@
+use crate::{Error, NoDelay, SevenSegInterface};
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::serial::Write;
+use nb::block;
+
+#[non_exhaustive]
+pub enum UartError<TX> {
+    Tx(TX),
+}
+
+pub struct SevSegUart<TX, D = NoDelay> {
+    tx: TX,
+    delay: D,
+    delay_us: u8,
+}
+
+impl<TX> SevSegUart<TX, NoDelay> {
+    /// Create a new SparkFun Serial Seven Segment display using a UART
+    /// (serial) port. The display defaults to 9600 baud.
+    pub fn new(tx: TX) -> Self {
+        Self {
+            tx,
+            delay: NoDelay,
+            delay_us: 15,
+        }
+    }
+}
+
+impl<TX, D> SevSegUart<TX, D>
+where
+    D: DelayUs<u8>,
+{
+    /// Create a new SparkFun Serial Seven Segment display using a UART
+    /// (serial) port, with a delay provider used to insert a guard delay
+    /// (default ~15us) between back-to-back commands. The display
+    /// defaults to 9600 baud.
+    pub fn new_with_delay(tx: TX, delay: D) -> Self {
+        Self {
+            tx,
+            delay,
+            delay_us: 15,
+        }
+    }
+}
+
+impl<TX, D> SevSegUart<TX, D> {
+    /// Change the guard delay (in microseconds) inserted between
+    /// back-to-back commands. Defaults to 15us. Has no effect unless this
+    /// was constructed with `new_with_delay()`.
+    pub fn set_command_delay(&mut self, delay_us: u8) {
+        self.delay_us = delay_us;
+    }
+
+    /// Release the components
+    pub fn release(self) -> TX {
+        self.tx
+    }
+}
+
+impl<TX, D> SevenSegInterface for SevSegUart<TX, D>
+where
+    TX: Write<u8>,
+    D: DelayUs<u8>,
+{
+    type InterfaceError = UartError<TX::Error>;
+
+    fn send(&mut self, data: &[u8]) -> Result<(), Error<Self::InterfaceError>> {
+        for byte in data {
+            block!(self.tx.write(*byte)).map_err(|e| Error::Interface(UartError::Tx(e)))?;
+        }
+
+        block!(self.tx.flush()).map_err(|e| Error::Interface(UartError::Tx(e)))
+    }
+
+    fn command_delay(&mut self) {
+        self.delay.delay_us(self.delay_us);
+    }
+}